@@ -1,3 +1,5 @@
+use std::str::FromStr;
+
 use anyhow::{anyhow, Result};
 
 #[repr(C)]
@@ -14,53 +16,30 @@ impl Color {
         Self {r,g,b,a}
     }
 
-    pub const fn from_rgb(r: u8, b: u8, g: u8) -> Self {
+    pub const fn from_rgb(r: u8, g: u8, b: u8) -> Self {
         Self::from_rgba(r, g, b, 255)
     }
 
-    pub fn as_hsl(&self) -> HslColor {
-         // Taken and converted from: https://stackoverflow.com/a/9493060
-         let r = self.r as f64 / 255.0;
-         let g = self.g as f64 / 255.0;
-         let b = self.b as f64 / 255.0;
-         let vmax = r.max(g.max(b));
-         let vmin = r.min(g.min(b));
-         let l = (vmax + vmin) / 2.0;
-
-         if vmax == vmin {
-             return HslColor::new(0.0, 0.0, l); // achromatic
-         }
-
-         let d = vmax - vmin;
-         let s = if l > 0.5 {
-             d / (2.0 - vmax - vmin)
-         } else {
-             d / (vmax + vmin)
-         };
-
-         let mut h = (vmax + vmin) / 2.0;
-
-         if vmax == r {
-             h = (g - b) / d;
-             if g < b {
-                 h += 6.0
-             }
-         }
-
-         if vmax == g {
-             h = (b - r) / d + 2.0;
-         }
+}
 
-         if vmax == b {
-             h = (r - g) / d + 4.0;
-         }
+impl Color {
+    /// Interpolates between `a` and `b` in linear-RGB space, which avoids the
+    /// muddy midtones that mixing raw sRGB bytes produces.
+    pub fn lerp(a: Color, b: Color, t: f32) -> Color {
+        let t = t.clamp(0.0, 1.0) as f64;
 
-         h /= 6.0;
+        let mix = |ca: u8, cb: u8| -> u8 {
+            let la = srgb_to_linear(ca as f64 / 255.0);
+            let lb = srgb_to_linear(cb as f64 / 255.0);
+            let l = (1.0 - t) * la + t * lb;
+            (linear_to_srgb(l) * 255.0).round().clamp(0.0, 255.0) as u8
+        };
+        let mix_alpha = |aa: u8, ab: u8| -> u8 {
+            ((1.0 - t) * aa as f64 + t * ab as f64).round().clamp(0.0, 255.0) as u8
+        };
 
-         // The color conversion moves every value into the [0,1] number space.
-         // But we want the hue in [0,360], s in [0,100] and l in [0,100]
-         HslColor::new(h * 360f64, s * 100f64, l * 100f64)
-     }
+        Color::from_rgba(mix(a.r, b.r), mix(a.g, b.g), mix(a.b, b.b), mix_alpha(a.a, b.a))
+    }
 }
 
 impl From<HslColor> for Color {
@@ -132,3 +111,759 @@ impl HslColor {
         Self { h, s, l }
     }
 }
+
+// D65 white point Y, used by the XYZ<->Luv conversions below (X and Z only
+// ever appear pre-divided out in the u'/v' ratios, via `LUV_REF_U`/`LUV_REF_V`).
+const WHITE_YN: f64 = 1.0;
+
+fn srgb_to_linear(c: f64) -> f64 {
+    if c <= 0.04045 {
+        c / 12.92
+    } else {
+        ((c + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+fn linear_to_srgb(c: f64) -> f64 {
+    if c <= 0.0031308 {
+        c * 12.92
+    } else {
+        1.055 * c.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+pub struct Xyz {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+impl Xyz {
+    fn from_linear_rgb(r: f64, g: f64, b: f64) -> Self {
+        Self {
+            x: 0.4124 * r + 0.3576 * g + 0.1805 * b,
+            y: 0.2126 * r + 0.7152 * g + 0.0722 * b,
+            z: 0.0193 * r + 0.1192 * g + 0.9505 * b,
+        }
+    }
+}
+
+fn lab_f(t: f64) -> f64 {
+    const DELTA: f64 = 6.0 / 29.0;
+    if t > DELTA.powi(3) {
+        t.cbrt()
+    } else {
+        t / (3.0 * DELTA * DELTA) + 4.0 / 29.0
+    }
+}
+
+fn lab_f_inv(t: f64) -> f64 {
+    const DELTA: f64 = 6.0 / 29.0;
+    if t > DELTA {
+        t.powi(3)
+    } else {
+        3.0 * DELTA * DELTA * (t - 4.0 / 29.0)
+    }
+}
+
+// D65 XYZ -> linear-sRGB matrix, the inverse of `Xyz::from_linear_rgb`.
+const XYZ_TO_LINEAR_RGB: [[f64; 3]; 3] = [
+    [3.2406, -1.5372, -0.4986],
+    [-0.9689, 1.8758, 0.0415],
+    [0.0557, -0.2040, 1.0570],
+];
+
+impl From<Xyz> for Color {
+    fn from(xyz: Xyz) -> Self {
+        let [m_r, m_g, m_b] = XYZ_TO_LINEAR_RGB;
+        let dot = |m: [f64; 3]| m[0] * xyz.x + m[1] * xyz.y + m[2] * xyz.z;
+
+        let to_byte = |c: f64| {
+            (linear_to_srgb(c.clamp(0.0, 1.0)) * 255.0)
+                .round()
+                .clamp(0.0, 255.0) as u8
+        };
+
+        Color::from_rgba(to_byte(dot(m_r)), to_byte(dot(m_g)), to_byte(dot(m_b)), 255)
+    }
+}
+
+// Reference white in CIELUV u',v' coordinates for the D65 white point
+// (the same white point whose Y is `WHITE_YN` above).
+const LUV_REF_U: f64 = 0.19783000664283681;
+const LUV_REF_V: f64 = 0.468_319_994_938_791;
+const LUV_KAPPA: f64 = 903.2962962;
+const LUV_EPSILON: f64 = 0.0088564516;
+
+struct LuvColor {
+    l: f64,
+    u: f64,
+    v: f64,
+}
+
+struct LchColor {
+    l: f64,
+    c: f64,
+    h: f64,
+}
+
+fn xyz_to_luv(xyz: &Xyz) -> LuvColor {
+    let l = 116.0 * lab_f(xyz.y / WHITE_YN) - 16.0;
+    if l <= 0.0 {
+        return LuvColor { l: 0.0, u: 0.0, v: 0.0 };
+    }
+
+    let denom = xyz.x + 15.0 * xyz.y + 3.0 * xyz.z;
+    let (up, vp) = if denom != 0.0 {
+        (4.0 * xyz.x / denom, 9.0 * xyz.y / denom)
+    } else {
+        (0.0, 0.0)
+    };
+
+    LuvColor {
+        l,
+        u: 13.0 * l * (up - LUV_REF_U),
+        v: 13.0 * l * (vp - LUV_REF_V),
+    }
+}
+
+fn luv_to_xyz(luv: &LuvColor) -> Xyz {
+    if luv.l <= 0.0 {
+        return Xyz { x: 0.0, y: 0.0, z: 0.0 };
+    }
+
+    let up = luv.u / (13.0 * luv.l) + LUV_REF_U;
+    let vp = luv.v / (13.0 * luv.l) + LUV_REF_V;
+
+    let y = WHITE_YN * lab_f_inv((luv.l + 16.0) / 116.0);
+    let x = y * 9.0 * up / (4.0 * vp);
+    let z = y * (12.0 - 3.0 * up - 20.0 * vp) / (4.0 * vp);
+
+    Xyz { x, y, z }
+}
+
+fn luv_to_lch(luv: LuvColor) -> LchColor {
+    let c = (luv.u * luv.u + luv.v * luv.v).sqrt();
+    let h = if c < 1e-8 {
+        0.0
+    } else {
+        let deg = luv.v.atan2(luv.u).to_degrees();
+        if deg < 0.0 { deg + 360.0 } else { deg }
+    };
+    LchColor { l: luv.l, c, h }
+}
+
+fn lch_to_luv(lch: &LchColor) -> LuvColor {
+    let hrad = lch.h.to_radians();
+    LuvColor {
+        l: lch.l,
+        u: hrad.cos() * lch.c,
+        v: hrad.sin() * lch.c,
+    }
+}
+
+// The gamut boundary, as up to 6 lines in (chroma, hue) space, that bounds
+// the in-gamut sRGB chroma at a given lightness `l`. Each row of
+// `XYZ_TO_LINEAR_RGB` contributes the two clip lines (black point, white
+// point) for that channel. See the HSLuv reference algorithm.
+fn get_bounds(l: f64) -> Vec<(f64, f64)> {
+    let sub1 = (l + 16.0).powi(3) / 1560896.0;
+    let sub2 = if sub1 > LUV_EPSILON { sub1 } else { l / LUV_KAPPA };
+
+    let mut bounds = Vec::with_capacity(6);
+    for &[m1, m2, m3] in XYZ_TO_LINEAR_RGB.iter() {
+        for t in 0..2 {
+            let t = t as f64;
+            let top1 = (284517.0 * m1 - 94839.0 * m3) * sub2;
+            let top2 = (838422.0 * m3 + 769860.0 * m2 + 731718.0 * m1) * l * sub2 - 769860.0 * t * l;
+            let bottom = (632260.0 * m3 - 126452.0 * m2) * sub2 + 126452.0 * t;
+            bounds.push((top1 / bottom, top2 / bottom));
+        }
+    }
+    bounds
+}
+
+fn max_chroma_for_lh(l: f64, h: f64) -> f64 {
+    let hrad = h.to_radians();
+    get_bounds(l)
+        .into_iter()
+        .filter_map(|(m, b)| {
+            let length = b / (hrad.sin() - m * hrad.cos());
+            (length >= 0.0).then_some(length)
+        })
+        .fold(f64::INFINITY, f64::min)
+}
+
+/// HSLuv: human-friendly HSL built on CIELUV, where `s` and `l` can be varied
+/// independently without shifting perceived hue or blowing out of gamut.
+#[derive(Clone, Copy)]
+pub struct HsluvColor {
+    pub h: f64, // Hue in [0,360]
+    pub s: f64, // Saturation in [0,100]
+    pub l: f64, // Lightness in [0,100]
+}
+
+impl HsluvColor {
+    pub fn new(h: f64, s: f64, l: f64) -> Self {
+        Self { h, s, l }
+    }
+}
+
+impl Color {
+    pub fn as_hsluv(&self) -> HsluvColor {
+        let r = srgb_to_linear(self.r as f64 / 255.0);
+        let g = srgb_to_linear(self.g as f64 / 255.0);
+        let b = srgb_to_linear(self.b as f64 / 255.0);
+        let lch = luv_to_lch(xyz_to_luv(&Xyz::from_linear_rgb(r, g, b)));
+
+        if lch.l > 99.9999999 {
+            return HsluvColor::new(lch.h, 0.0, 100.0);
+        }
+        if lch.l < 0.00000001 {
+            return HsluvColor::new(lch.h, 0.0, 0.0);
+        }
+
+        let max_chroma = max_chroma_for_lh(lch.l, lch.h);
+        HsluvColor::new(lch.h, (lch.c / max_chroma * 100.0).clamp(0.0, 100.0), lch.l)
+    }
+}
+
+impl From<HsluvColor> for Color {
+    fn from(v: HsluvColor) -> Self {
+        let (l, c) = if v.l > 99.9999999 {
+            (100.0, 0.0)
+        } else if v.l < 0.00000001 {
+            (0.0, 0.0)
+        } else {
+            let max_chroma = max_chroma_for_lh(v.l, v.h);
+            (v.l, max_chroma / 100.0 * v.s)
+        };
+
+        luv_to_xyz(&lch_to_luv(&LchColor { l, c, h: v.h })).into()
+    }
+}
+
+/// Derives an evenly `L`-spaced HSLuv ramp from a single seed color, holding
+/// hue and saturation steady so the tail darkens without drifting hue or
+/// collapsing saturation. Suitable as a `Gradient`'s stops.
+pub fn palette_from_seed(seed: Color, steps: usize) -> Gradient {
+    const HEAD_L: f64 = 95.0;
+    const TAIL_L: f64 = 8.0;
+
+    let base = seed.as_hsluv();
+    let steps = steps.max(2);
+
+    let stops = (0..steps)
+        .map(|i| {
+            let t = i as f32 / (steps - 1) as f32;
+            let l = HEAD_L + (TAIL_L - HEAD_L) * t as f64;
+            (t, HsluvColor::new(base.h, base.s, l).into())
+        })
+        .collect();
+
+    Gradient::new(stops)
+}
+
+const NAMED_COLORS: &[(&str, Color)] = &[
+    ("black", Color::from_rgba(0, 0, 0, 255)),
+    ("white", Color::from_rgba(255, 255, 255, 255)),
+    ("red", Color::from_rgba(255, 0, 0, 255)),
+    ("green", Color::from_rgba(0, 255, 0, 255)),
+    ("blue", Color::from_rgba(0, 0, 255, 255)),
+    ("cyan", Color::from_rgba(0, 255, 255, 255)),
+    ("magenta", Color::from_rgba(255, 0, 255, 255)),
+    ("yellow", Color::from_rgba(255, 255, 0, 255)),
+    ("orange", Color::from_rgba(255, 165, 0, 255)),
+    ("amber", Color::from_rgba(255, 191, 0, 255)),
+    ("purple", Color::from_rgba(128, 0, 128, 255)),
+    ("pink", Color::from_rgba(255, 192, 203, 255)),
+    ("gray", Color::from_rgba(128, 128, 128, 255)),
+    ("grey", Color::from_rgba(128, 128, 128, 255)),
+];
+
+impl FromStr for Color {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let s = s.trim().to_lowercase();
+
+        if let Some(hex) = s.strip_prefix('#') {
+            return parse_hex(hex);
+        }
+        if let Some(inner) = s.strip_prefix("rgba(").and_then(|v| v.strip_suffix(')')) {
+            return parse_rgb(inner, true);
+        }
+        if let Some(inner) = s.strip_prefix("rgb(").and_then(|v| v.strip_suffix(')')) {
+            return parse_rgb(inner, false);
+        }
+        if let Some(inner) = s.strip_prefix("hsl(").and_then(|v| v.strip_suffix(')')) {
+            return parse_hsl(inner);
+        }
+        if let Some((_, color)) = NAMED_COLORS.iter().find(|(name, _)| *name == s) {
+            return Ok(*color);
+        }
+
+        Err(anyhow!(
+            "unrecognized color '{}' (expected #rgb/#rrggbb/#rrggbbaa hex, \
+             rgb(r,g,b), rgba(r,g,b,a), hsl(h,s%,l%), or a named color)",
+            s
+        ))
+    }
+}
+
+fn parse_hex(hex: &str) -> Result<Color> {
+    fn nibble_pair(c: char) -> String {
+        let mut s = String::new();
+        s.push(c);
+        s.push(c);
+        s
+    }
+
+    if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(anyhow!("invalid hex digits in '#{}'", hex));
+    }
+
+    let expanded = match hex.chars().count() {
+        3 | 4 => hex.chars().map(nibble_pair).collect::<String>(),
+        6 | 8 => hex.to_string(),
+        _ => return Err(anyhow!("hex color '#{}' must have 3, 4, 6 or 8 digits", hex)),
+    };
+
+    let byte = |i: usize| -> Result<u8> {
+        u8::from_str_radix(&expanded[i * 2..i * 2 + 2], 16)
+            .map_err(|_| anyhow!("invalid hex digits in '#{}'", hex))
+    };
+
+    let r = byte(0)?;
+    let g = byte(1)?;
+    let b = byte(2)?;
+    let a = if expanded.len() == 8 { byte(3)? } else { 255 };
+
+    Ok(Color::from_rgba(r, g, b, a))
+}
+
+fn parse_rgb(inner: &str, with_alpha: bool) -> Result<Color> {
+    let parts: Vec<&str> = inner.split(',').map(|p| p.trim()).collect();
+    let expected = if with_alpha { 4 } else { 3 };
+    if parts.len() != expected {
+        return Err(anyhow!(
+            "expected {} comma-separated values in '{}(...)'",
+            expected,
+            if with_alpha { "rgba" } else { "rgb" }
+        ));
+    }
+
+    let channel = |s: &str| -> Result<u8> {
+        s.parse::<u8>()
+            .map_err(|_| anyhow!("invalid channel value '{}', expected 0-255", s))
+    };
+
+    let r = channel(parts[0])?;
+    let g = channel(parts[1])?;
+    let b = channel(parts[2])?;
+
+    let a = if with_alpha {
+        let raw = parts[3];
+        if let Ok(f) = raw.parse::<f64>() {
+            if f <= 1.0 {
+                (f * 255.0).round() as u8
+            } else {
+                f.round().clamp(0.0, 255.0) as u8
+            }
+        } else {
+            return Err(anyhow!("invalid alpha value '{}', expected 0-1 or 0-255", raw));
+        }
+    } else {
+        255
+    };
+
+    Ok(Color::from_rgba(r, g, b, a))
+}
+
+// 8.8 fixed-point scale: a multiplier of 1.0 is stored as 256.
+const FIXED_POINT_SHIFT: u32 = 8;
+const FIXED_POINT_ONE: i32 = 1 << FIXED_POINT_SHIFT;
+
+/// A per-channel affine color transform (`out = in * mult + add`), applied in
+/// the hot render loop for brightness/contrast/tint effects. Multipliers are
+/// stored as 8.8 fixed-point integers so applying a transform is a multiply,
+/// a shift and an add per channel — no floats in the hot path.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct ColorTransform {
+    r_mult: i32,
+    g_mult: i32,
+    b_mult: i32,
+    a_mult: i32,
+    r_add: i32,
+    g_add: i32,
+    b_add: i32,
+    a_add: i32,
+}
+
+impl ColorTransform {
+    pub const IDENTITY: ColorTransform = ColorTransform {
+        r_mult: FIXED_POINT_ONE,
+        g_mult: FIXED_POINT_ONE,
+        b_mult: FIXED_POINT_ONE,
+        a_mult: FIXED_POINT_ONE,
+        r_add: 0,
+        g_add: 0,
+        b_add: 0,
+        a_add: 0,
+    };
+
+    fn to_fixed(factor: f32) -> i32 {
+        (factor * FIXED_POINT_ONE as f32).round() as i32
+    }
+
+    /// Scales every channel (alpha untouched) by `factor`; `factor > 1.0` brightens.
+    pub fn brightness(factor: f32) -> Self {
+        Self {
+            r_mult: Self::to_fixed(factor),
+            g_mult: Self::to_fixed(factor),
+            b_mult: Self::to_fixed(factor),
+            ..Self::IDENTITY
+        }
+    }
+
+    /// Scales each channel's distance from mid-gray by `factor`; `factor > 1.0` increases contrast.
+    pub fn contrast(factor: f32) -> Self {
+        let mult = Self::to_fixed(factor);
+        let add = (128.0 * (1.0 - factor)).round() as i32;
+        Self {
+            r_mult: mult,
+            g_mult: mult,
+            b_mult: mult,
+            r_add: add,
+            g_add: add,
+            b_add: add,
+            ..Self::IDENTITY
+        }
+    }
+
+    /// Blends every channel toward `color` by `amount` (`0.0` = no tint, `1.0` = fully replaced).
+    pub fn tint(color: Color, amount: f32) -> Self {
+        let mult = Self::to_fixed(1.0 - amount);
+        let add_for = |c: u8| (c as f32 * amount).round() as i32;
+        Self {
+            r_mult: mult,
+            g_mult: mult,
+            b_mult: mult,
+            r_add: add_for(color.r),
+            g_add: add_for(color.g),
+            b_add: add_for(color.b),
+            ..Self::IDENTITY
+        }
+    }
+
+    /// Composes `self` then `other`, so `self.compose(&other).apply(c) == other.apply(self.apply(c))`.
+    pub fn compose(&self, other: &ColorTransform) -> Self {
+        let compose_channel = |m1: i32, a1: i32, m2: i32, a2: i32| -> (i32, i32) {
+            let mult = ((m1 as i64 * m2 as i64) >> FIXED_POINT_SHIFT) as i32;
+            let add = (((a1 as i64 * m2 as i64) >> FIXED_POINT_SHIFT) as i32) + a2;
+            (mult, add)
+        };
+
+        let (r_mult, r_add) = compose_channel(self.r_mult, self.r_add, other.r_mult, other.r_add);
+        let (g_mult, g_add) = compose_channel(self.g_mult, self.g_add, other.g_mult, other.g_add);
+        let (b_mult, b_add) = compose_channel(self.b_mult, self.b_add, other.b_mult, other.b_add);
+        let (a_mult, a_add) = compose_channel(self.a_mult, self.a_add, other.a_mult, other.a_add);
+
+        Self { r_mult, g_mult, b_mult, a_mult, r_add, g_add, b_add, a_add }
+    }
+
+    pub fn apply(&self, color: Color) -> Color {
+        let apply_channel = |c: u8, mult: i32, add: i32| -> u8 {
+            let scaled = ((c as i32 * mult) >> FIXED_POINT_SHIFT) + add;
+            scaled.clamp(0, 255) as u8
+        };
+
+        Color::from_rgba(
+            apply_channel(color.r, self.r_mult, self.r_add),
+            apply_channel(color.g, self.g_mult, self.g_add),
+            apply_channel(color.b, self.b_mult, self.b_add),
+            apply_channel(color.a, self.a_mult, self.a_add),
+        )
+    }
+}
+
+/// A multi-stop color ramp sampled by position `t` in `[0,1]`, used to paint a
+/// trail from a bright leading head down through the base color to dark.
+#[derive(Clone)]
+pub struct Gradient {
+    pub stops: Vec<(f32, Color)>,
+}
+
+impl Gradient {
+    pub fn new(stops: Vec<(f32, Color)>) -> Self {
+        Self { stops }
+    }
+
+    pub fn sample(&self, t: f32) -> Color {
+        let t = t.clamp(0.0, 1.0);
+
+        let first = match self.stops.first() {
+            Some(stop) => stop,
+            None => return Color::from_rgba(0, 0, 0, 255),
+        };
+        let last = self.stops.last().unwrap();
+
+        if t <= first.0 {
+            return first.1;
+        }
+        if t >= last.0 {
+            return last.1;
+        }
+
+        for window in self.stops.windows(2) {
+            let (t0, c0) = window[0];
+            let (t1, c1) = window[1];
+            if t >= t0 && t <= t1 {
+                let local_t = if t1 > t0 { (t - t0) / (t1 - t0) } else { 0.0 };
+                return Color::lerp(c0, c1, local_t);
+            }
+        }
+
+        last.1
+    }
+}
+
+fn parse_hsl(inner: &str) -> Result<Color> {
+    let parts: Vec<&str> = inner.split(',').map(|p| p.trim()).collect();
+    if parts.len() != 3 {
+        return Err(anyhow!("expected 3 comma-separated values in 'hsl(...)'"));
+    }
+
+    let h = parts[0]
+        .parse::<f64>()
+        .map_err(|_| anyhow!("invalid hue '{}'", parts[0]))?;
+    let s = parts[1]
+        .strip_suffix('%')
+        .ok_or_else(|| anyhow!("saturation '{}' must end in %", parts[1]))?
+        .parse::<f64>()
+        .map_err(|_| anyhow!("invalid saturation '{}'", parts[1]))?;
+    let l = parts[2]
+        .strip_suffix('%')
+        .ok_or_else(|| anyhow!("lightness '{}' must end in %", parts[2]))?
+        .parse::<f64>()
+        .map_err(|_| anyhow!("invalid lightness '{}'", parts[2]))?;
+
+    Ok(HslColor::new(h, s, l).into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hex_shorthand_expands_each_nibble() {
+        assert_eq!(
+            Color::from_str("#0f3").unwrap(),
+            Color::from_rgba(0x00, 0xff, 0x33, 255)
+        );
+        assert_eq!(
+            Color::from_str("#0f38").unwrap(),
+            Color::from_rgba(0x00, 0xff, 0x33, 0x88)
+        );
+    }
+
+    #[test]
+    fn hex_full_forms() {
+        assert_eq!(
+            Color::from_str("#00ff2b").unwrap(),
+            Color::from_rgba(0, 255, 43, 255)
+        );
+        assert_eq!(
+            Color::from_str("#00ff2b80").unwrap(),
+            Color::from_rgba(0, 255, 43, 0x80)
+        );
+    }
+
+    #[test]
+    fn hex_rejects_bad_length_and_digits() {
+        assert!(Color::from_str("#0f").is_err());
+        assert!(Color::from_str("#gggggg").is_err());
+    }
+
+    #[test]
+    fn hex_rejects_non_ascii_without_panicking() {
+        assert!(Color::from_str("#a€").is_err());
+        assert!(Color::from_str("#ffffff€").is_err());
+    }
+
+    #[test]
+    fn rgb_parses_three_channels() {
+        assert_eq!(
+            Color::from_str("rgb(0, 255, 43)").unwrap(),
+            Color::from_rgba(0, 255, 43, 255)
+        );
+    }
+
+    #[test]
+    fn rgba_accepts_float_or_integer_alpha() {
+        assert_eq!(
+            Color::from_str("rgba(255, 0, 0, 0.5)").unwrap(),
+            Color::from_rgba(255, 0, 0, 128)
+        );
+        assert_eq!(
+            Color::from_str("rgba(255, 0, 0, 128)").unwrap(),
+            Color::from_rgba(255, 0, 0, 128)
+        );
+    }
+
+    #[test]
+    fn rgb_rejects_wrong_arity_and_out_of_range_channel() {
+        assert!(Color::from_str("rgb(0, 255)").is_err());
+        assert!(Color::from_str("rgb(0, 255, 256)").is_err());
+    }
+
+    #[test]
+    fn hsl_requires_percent_signs() {
+        assert!(Color::from_str("hsl(120, 50%, 50%)").is_ok());
+        assert!(Color::from_str("hsl(120, 50, 50%)").is_err());
+    }
+
+    #[test]
+    fn named_colors_are_case_insensitive() {
+        assert_eq!(
+            Color::from_str("GREEN").unwrap(),
+            Color::from_rgba(0, 255, 0, 255)
+        );
+        assert_eq!(
+            Color::from_str("amber").unwrap(),
+            Color::from_rgba(255, 191, 0, 255)
+        );
+    }
+
+    #[test]
+    fn unrecognized_input_is_an_error() {
+        assert!(Color::from_str("not-a-color").is_err());
+    }
+
+    #[test]
+    fn hsluv_round_trips_through_rgb() {
+        for color in [
+            Color::from_rgba(255, 0, 0, 255),
+            Color::from_rgba(0, 255, 0, 255),
+            Color::from_rgba(0, 0, 255, 255),
+            Color::from_rgba(0, 255, 43, 255),
+            Color::from_rgba(128, 64, 200, 255),
+        ] {
+            let back: Color = color.as_hsluv().into();
+            assert!(
+                (back.r as i16 - color.r as i16).abs() <= 1
+                    && (back.g as i16 - color.g as i16).abs() <= 1
+                    && (back.b as i16 - color.b as i16).abs() <= 1,
+                "{:?} round-tripped to {:?} via HSLuv",
+                color,
+                back
+            );
+        }
+    }
+
+    #[test]
+    fn hsluv_matches_known_reference_value_for_pure_red() {
+        // Reference values from the published HSLuv conversion table.
+        let hsluv = Color::from_rgba(255, 0, 0, 255).as_hsluv();
+        assert!((hsluv.h - 12.18).abs() < 0.1);
+        assert!((hsluv.s - 100.0).abs() < 0.1);
+        assert!((hsluv.l - 53.23).abs() < 0.1);
+    }
+
+    #[test]
+    fn hsluv_black_and_white_have_zero_saturation() {
+        let black = Color::from_rgba(0, 0, 0, 255).as_hsluv();
+        assert_eq!(black.l, 0.0);
+        assert_eq!(black.s, 0.0);
+
+        let white = Color::from_rgba(255, 255, 255, 255).as_hsluv();
+        assert!((white.l - 100.0).abs() < 1e-6);
+        assert_eq!(white.s, 0.0);
+    }
+
+    #[test]
+    fn gradient_sample_returns_exact_stops_at_their_t() {
+        let gradient = Gradient::new(vec![
+            (0.0, Color::from_rgba(255, 255, 255, 255)),
+            (0.5, Color::from_rgba(0, 255, 0, 255)),
+            (1.0, Color::from_rgba(0, 0, 0, 255)),
+        ]);
+
+        assert_eq!(gradient.sample(0.0), Color::from_rgba(255, 255, 255, 255));
+        assert_eq!(gradient.sample(0.5), Color::from_rgba(0, 255, 0, 255));
+        assert_eq!(gradient.sample(1.0), Color::from_rgba(0, 0, 0, 255));
+    }
+
+    #[test]
+    fn gradient_sample_clamps_out_of_range_t() {
+        let gradient = Gradient::new(vec![
+            (0.0, Color::from_rgba(255, 255, 255, 255)),
+            (1.0, Color::from_rgba(0, 0, 0, 255)),
+        ]);
+
+        assert_eq!(gradient.sample(-1.0), Color::from_rgba(255, 255, 255, 255));
+        assert_eq!(gradient.sample(2.0), Color::from_rgba(0, 0, 0, 255));
+    }
+
+    #[test]
+    fn gradient_sample_interpolates_between_bracketing_stops() {
+        let gradient = Gradient::new(vec![
+            (0.0, Color::from_rgba(0, 0, 0, 255)),
+            (1.0, Color::from_rgba(255, 255, 255, 255)),
+        ]);
+
+        let mid = gradient.sample(0.5);
+        assert!(mid.r > 0 && mid.r < 255);
+        assert_eq!(mid.r, mid.g);
+        assert_eq!(mid.g, mid.b);
+    }
+
+    #[test]
+    fn color_transform_identity_round_trips() {
+        let c = Color::from_rgba(10, 20, 30, 40);
+        assert_eq!(ColorTransform::IDENTITY.apply(c), c);
+    }
+
+    #[test]
+    fn color_transform_brightness_scales_channels() {
+        let transform = ColorTransform::brightness(0.5);
+        let out = transform.apply(Color::from_rgba(200, 100, 50, 255));
+        assert_eq!(out, Color::from_rgba(100, 50, 25, 255));
+    }
+
+    #[test]
+    fn color_transform_contrast_pushes_toward_extremes() {
+        let transform = ColorTransform::contrast(2.0);
+        // Above mid-gray gets pushed up, below mid-gray gets pushed down.
+        let bright = transform.apply(Color::from_rgba(200, 200, 200, 255));
+        let dark = transform.apply(Color::from_rgba(50, 50, 50, 255));
+        assert_eq!(bright, Color::from_rgba(255, 255, 255, 255));
+        assert_eq!(dark, Color::from_rgba(0, 0, 0, 255));
+    }
+
+    #[test]
+    fn color_transform_compose_matches_sequential_apply() {
+        let a = ColorTransform::brightness(0.8);
+        let b = ColorTransform::contrast(1.2);
+        let c = Color::from_rgba(120, 80, 200, 255);
+
+        let composed = a.compose(&b).apply(c);
+        let sequential = b.apply(a.apply(c));
+
+        // 8.8 fixed-point rounding can differ from the sequential application
+        // by a shade, so allow an off-by-one per channel.
+        for (x, y) in [
+            (composed.r, sequential.r),
+            (composed.g, sequential.g),
+            (composed.b, sequential.b),
+            (composed.a, sequential.a),
+        ] {
+            assert!(
+                (x as i16 - y as i16).abs() <= 1,
+                "composed {:?} vs sequential {:?}",
+                composed,
+                sequential
+            );
+        }
+    }
+}
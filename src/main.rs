@@ -1,9 +1,11 @@
 mod color;
-use std::{io::Write, time::{Duration, SystemTime}};
+mod noise;
+use std::{io::Write, str::FromStr, time::{Duration, SystemTime}};
 
 use anyhow::{Context, Result};
 use crossterm::{terminal, cursor, queue, style};
-use color::{Color, HslColor};
+use color::{Color, ColorTransform, Gradient};
+use noise::FbmNoise;
 
 use rand_xoshiro::rand_core::SeedableRng;
 use rand_xoshiro::Xoshiro256PlusPlus;
@@ -23,11 +25,12 @@ impl Glyph {
         }
     }
 
-    fn fade_color(&mut self){
-        let hsl = self.color.as_hsl();
-        self.color = HslColor::new(hsl.h, hsl.s*0.8, hsl.l*0.8).into();
-    }
-    fn render<W: Write> (&self, out: &mut W)-> Result<()>{
+    fn render<W: Write> (&self, out: &mut W, transform: Option<&ColorTransform>)-> Result<()>{
+        let color = match transform {
+            Some(t) => t.apply(self.color),
+            None => self.color,
+        };
+
         queue!(
             out,
             style::SetBackgroundColor(style::Color::Rgb {
@@ -36,7 +39,7 @@ impl Glyph {
         queue!(
             out,
             style::SetForegroundColor(style::Color::Rgb
-                { r: self.color.r, g: self.color.g, b:self.color.b })
+                { r: color.r, g: color.g, b: color.b })
         )?;
         queue!(out, style::Print(self.character.to_string()))?;
         out.write(self.character.to_string().as_bytes()).context("write glyphs")?;
@@ -63,12 +66,22 @@ impl Glyph {
     }
 }
 
+// Builds the head-to-tail ramp from the column's base color: an HSLuv
+// lightness sweep keeps the hue and saturation steady all the way down, so
+// the tail darkens without drifting hue or collapsing saturation. This
+// gradient sampling supersedes the earlier per-step CIELAB/HSL glyph fade
+// (and its mode flag) entirely; neither exists in the tree anymore.
+fn trail_gradient(base_color: Color) -> Gradient {
+    color::palette_from_seed(base_color, 6)
+}
+
 #[derive(Clone)]
 struct Column {
     height: u16,
     base_color: Color,
     glyphs: Vec<Glyph>,
     active_index: usize,
+    gradient: Gradient,
 }
 
 impl Column {
@@ -79,55 +92,146 @@ impl Column {
             height,
             base_color,
             glyphs,
-            active_index:0
+            active_index:0,
+            gradient: trail_gradient(base_color),
         }
     }
 
     fn empty(height: u16) ->Self{
+        let base_color = Color::from_rgba(0,0,0,255);
         Self{
             height,
-            base_color: Color::from_rgba(0,0,0,255),
+            base_color,
             glyphs: vec![Glyph::empty(); height as usize],
-            active_index: 0
+            active_index: 0,
+            gradient: trail_gradient(base_color),
         }
     }
-    fn render<W: Write>(&self, out: &mut W, y: u16)-> Result<()>{
-        self.glyphs[y as usize].render(out)?;
+    fn render<W: Write>(&self, out: &mut W, y: u16, transform: Option<&ColorTransform>)-> Result<()>{
+        self.glyphs[y as usize].render(out, transform)?;
         Ok(())
     }
-    fn step<R: Rng>(&mut self, rand: &mut R){
 
-        if self.active_index==0 && rand.gen::<f32>() >0.1{
+    // Colors every glyph by its distance behind `head`, so the leading
+    // character is brightest and older ones fade down the gradient.
+    fn apply_gradient(&mut self, head: usize) {
+        let height = self.height as usize;
+        for y in 0..height {
+            let offset = (head + height - y) % height;
+            let t = offset as f32 / height as f32;
+            self.glyphs[y].color = self.gradient.sample(t);
+        }
+    }
+
+    // `turbulence` in `[0,1]` comes from the waterfall's noise field: it
+    // raises the chance an idle column starts a new run and how many rows
+    // the head advances this tick, so neighboring columns gust together.
+    fn step<R: Rng>(&mut self, rand: &mut R, turbulence: f32){
+
+        let advance_threshold = 0.05 + turbulence * 0.25;
+        if self.active_index==0 && rand.gen::<f32>() > advance_threshold{
             return;
         }
 
-        for glyph in &mut self.glyphs {
-            glyph.fade_color();
+        let rows_to_advance = 1 + (turbulence * 2.0).round() as usize;
+        let mut head = self.active_index;
+
+        for _ in 0..rows_to_advance {
+            self.glyphs[self.active_index] = Glyph::new_random(rand,self.base_color);
+
+            head = self.active_index;
+            self.active_index+=1;
+
+            if self.active_index >=self.height as usize{
+                self.active_index = 0
+            }
         }
 
-        self.glyphs[self.active_index] = Glyph::new_random(rand,self.base_color);
-        self.active_index+=1;
+        self.apply_gradient(head);
+    }
+}
+
+// How far apart (in columns) the noise field is sampled, and how fast it
+// drifts over time. Lower frequency means broader, slower-moving gusts.
+const TURBULENCE_SPATIAL_FREQ: f64 = 0.1;
+const TURBULENCE_TIME_SPEED: f64 = 0.6;
+
+// How fast `EffectMode::Pulse` cycles brightness, in radians per tick.
+const PULSE_SPEED: f64 = 0.05;
+
+// Selects which `ColorTransform` (if any) `MatrixWaterfall::render` runs
+// every glyph color through, set via the second CLI arg / `MATRIX_EFFECT`.
+#[derive(Clone, Copy)]
+enum EffectMode {
+    None,
+    Dim,
+    Bright,
+    Contrast,
+    Amber,
+    Blue,
+    Pulse,
+}
+
+impl FromStr for EffectMode {
+    type Err = anyhow::Error;
 
-        if self.active_index >=self.height as usize{
-            self.active_index = 0
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "none" => Ok(EffectMode::None),
+            "dim" => Ok(EffectMode::Dim),
+            "bright" => Ok(EffectMode::Bright),
+            "contrast" => Ok(EffectMode::Contrast),
+            "amber" => Ok(EffectMode::Amber),
+            "blue" => Ok(EffectMode::Blue),
+            "pulse" => Ok(EffectMode::Pulse),
+            other => Err(anyhow::anyhow!(
+                "unrecognized effect '{}' (expected none/dim/bright/contrast/amber/blue/pulse)",
+                other
+            )),
         }
     }
 }
 
+// Picks the `ColorTransform` a given effect mode starts with. `Pulse` is
+// recomputed every tick in `MatrixWaterfall::step`, so its starting value
+// here only matters for the first frame.
+fn transform_for_mode(mode: EffectMode) -> Option<ColorTransform> {
+    const AMBER: Color = Color::from_rgba(255, 176, 0, 255);
+    const BLUE: Color = Color::from_rgba(0, 120, 255, 255);
+
+    match mode {
+        EffectMode::None => None,
+        EffectMode::Dim => Some(ColorTransform::brightness(0.5)),
+        EffectMode::Bright => Some(ColorTransform::brightness(1.5)),
+        EffectMode::Contrast => Some(ColorTransform::contrast(1.5)),
+        EffectMode::Amber => Some(ColorTransform::tint(AMBER, 0.45).compose(&ColorTransform::contrast(1.1))),
+        EffectMode::Blue => Some(ColorTransform::tint(BLUE, 0.45).compose(&ColorTransform::contrast(1.1))),
+        EffectMode::Pulse => Some(ColorTransform::brightness(1.0)),
+    }
+}
+
 struct MatrixWaterfall{
     height: u16,
     width: u16,
     base_color: Color,
-    columns: Vec<Column>
+    columns: Vec<Column>,
+    turbulence: FbmNoise,
+    time: f64,
+    effect_mode: EffectMode,
+    transform: Option<ColorTransform>,
 }
 
 impl MatrixWaterfall {
-    fn new(w: u16,h: u16,col:Color)-> Self{
+    fn new(w: u16,h: u16,col:Color, seed: u64, effect_mode: EffectMode)-> Self{
         Self{
             width: w,
             height: h,
             base_color: col,
-            columns: vec![Column::new(h,col); w as usize]
+            columns: vec![Column::new(h,col); w as usize],
+            turbulence: FbmNoise::new(seed),
+            time: 0.0,
+            transform: transform_for_mode(effect_mode),
+            effect_mode,
         }
     }
 
@@ -138,7 +242,7 @@ impl MatrixWaterfall {
 
         for y in 0..self.height{
             for column in &self.columns{
-                column.render(out,y)?;
+                column.render(out,y,self.transform.as_ref())?;
             }
         }
         queue!(out, cursor::Show)?;
@@ -149,23 +253,63 @@ impl MatrixWaterfall {
 
     fn step<R: RngCore>(&mut self, rand: &mut R){
 
-        for column in &mut self.columns {
-            column.step(rand);
+        self.time += 1.0;
+
+        if let EffectMode::Pulse = self.effect_mode {
+            let brightness = 1.0 + 0.3 * (self.time * PULSE_SPEED).sin() as f32;
+            self.transform = Some(ColorTransform::brightness(brightness));
         }
+
+        for (x, column) in self.columns.iter_mut().enumerate() {
+            let raw = self.turbulence.sample(x as f64 * TURBULENCE_SPATIAL_FREQ, self.time * TURBULENCE_TIME_SPEED);
+            let gust = ((raw + 1.0) / 2.0) as f32;
+            column.step(rand, gust);
+        }
+    }
+}
+
+fn base_color_from_env() -> Color {
+    let default = Color::from_rgba(0, 255, 43, 255);
+
+    let requested = std::env::args()
+        .nth(1)
+        .or_else(|| std::env::var("MATRIX_COLOR").ok());
+
+    match requested {
+        Some(raw) => Color::from_str(&raw).unwrap_or_else(|err| {
+            eprintln!("warning: {err}, falling back to default green");
+            default
+        }),
+        None => default,
+    }
+}
+
+fn effect_mode_from_env() -> EffectMode {
+    let requested = std::env::args()
+        .nth(2)
+        .or_else(|| std::env::var("MATRIX_EFFECT").ok());
+
+    match requested {
+        Some(raw) => EffectMode::from_str(&raw).unwrap_or_else(|err| {
+            eprintln!("warning: {err}, falling back to no effect");
+            EffectMode::None
+        }),
+        None => EffectMode::None,
     }
 }
 
 fn main() -> Result<()> {
 
     let (width, height) = terminal::size().context("determine terminal size")?;
-    let base_color = Color::from_rgba(0,255,43,255);
-    let mut waterfall = MatrixWaterfall::new(width, height, base_color);
-
-    let mut stdout = std::io::stdout();
+    let base_color = base_color_from_env();
+    let effect_mode = effect_mode_from_env();
 
     let seed = SystemTime::now().duration_since(SystemTime::UNIX_EPOCH).expect("some time").as_micros() as u64;
+    let mut waterfall = MatrixWaterfall::new(width, height, base_color, seed, effect_mode);
     let mut random = Xoshiro256PlusPlus::seed_from_u64(seed);
 
+    let mut stdout = std::io::stdout();
+
     loop{
         waterfall.render(&mut stdout)?;
         waterfall.step(&mut random);